@@ -6,6 +6,13 @@
 use std::io::Write;
 
 use libwebp::{Encoder, PixelLayout, WebPMemory};
+use libwebp::sys::{
+    WebPAnimEncoder, WebPAnimEncoderAdd, WebPAnimEncoderAssemble, WebPAnimEncoderDelete,
+    WebPAnimEncoderNew, WebPAnimEncoderOptions, WebPAnimEncoderOptionsInit, WebPConfig,
+    WebPConfigInit, WebPData, WebPDataClear, WebPEncode, WebPMemoryWrite, WebPMemoryWriter,
+    WebPMemoryWriterClear, WebPMemoryWriterInit, WebPMuxAnimParams, WebPPicture, WebPPictureFree,
+    WebPPictureImportRGB, WebPPictureImportRGBA, WebPPictureInit, WebPValidateConfig,
+};
 
 use crate::error::EncodingError;
 use crate::ImageFormat::WebP;
@@ -16,6 +23,7 @@ use crate::{ImageError, ImageFormat, ImageResult};
 pub struct WebPEncoder<W> {
     inner: W,
     quality: WebPQuality,
+    config: Option<WebPEncoderConfig>,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -65,13 +73,34 @@ impl<W: Write> WebPEncoder<W> {
 
     /// Create a new encoder with the specified quality, that writes its output to `w`.
     pub fn new_with_quality(w: W, quality: WebPQuality) -> Self {
-        Self { inner: w, quality }
+        Self {
+            inner: w,
+            quality,
+            config: None,
+        }
+    }
+
+    /// Create a new encoder driven by a full [`WebPEncoderConfig`], that writes its output to
+    /// `w`.
+    ///
+    /// Unlike [`new`]/[`new_with_quality`], which go through libwebp's simple encoding API, this
+    /// drives a `WebPConfig` + `WebPPicture` encode path, giving access to the full range of
+    /// tuning `config` exposes.
+    ///
+    /// [`new`]: Self::new
+    /// [`new_with_quality`]: Self::new_with_quality
+    pub fn new_with_config(w: W, config: WebPEncoderConfig) -> Self {
+        Self {
+            inner: w,
+            quality: config.quality,
+            config: Some(config),
+        }
     }
 
     /// Encode image data with the indicated color type.
     ///
-    /// The encoder requires all data to be RGB8 or RGBA8, it will be converted
-    /// internally if necessary.
+    /// `Luma8`/`LumaA8` are expanded to RGB8/RGBA8 and 16-bit color types are downconverted to
+    /// their 8-bit equivalents, since libwebp only understands packed 8-bit RGB/RGBA.
     pub fn encode(
         mut self,
         data: &[u8],
@@ -79,15 +108,19 @@ impl<W: Write> WebPEncoder<W> {
         height: u32,
         color: ColorType,
     ) -> ImageResult<()> {
-        // TODO: convert color types internally.
-        let layout: PixelLayout = match color {
-            ColorType::Rgb8 => PixelLayout::Rgb,
-            ColorType::Rgba8 => PixelLayout::Rgba,
-            _ => unimplemented!("Color type not yet supported"),
-        };
+        let (converted, layout) = convert_color(data, color)?;
+
+        if let Some(config) = self.config {
+            let mut writer = encode_with_config(&converted, width, height, layout, config)?;
+            let bytes = unsafe { std::slice::from_raw_parts(writer.mem, writer.size) };
+            let write_result = self.inner.write_all(bytes);
+            unsafe { WebPMemoryWriterClear(&mut writer) };
+            write_result?;
+            return Ok(());
+        }
 
         // Call the native libwebp library to encode the image.
-        let encoder = Encoder::new(data, layout, width, height);
+        let encoder = Encoder::new(&converted, layout, width, height);
         let encoded: WebPMemory = match self.quality.0 {
             Quality::Lossless => encoder.encode_lossless(),
             Quality::Lossy(quality) => encoder.encode(quality as f32),
@@ -106,6 +139,256 @@ impl<W: Write> WebPEncoder<W> {
     }
 }
 
+impl WebPEncoder<Vec<u8>> {
+    /// Encode image data with the indicated color type, returning the encoded WebP bytes
+    /// directly instead of requiring a `Write` sink.
+    ///
+    /// Defaults to lossy encoding, see [`WebPQuality::DEFAULT`]; use [`WebPEncoder::new_with_quality`]
+    /// or [`WebPEncoder::new_with_config`] with [`WebPEncoder::encode`] for other settings.
+    pub fn encode_to_vec(
+        data: &[u8],
+        width: u32,
+        height: u32,
+        color: ColorType,
+    ) -> ImageResult<Vec<u8>> {
+        let mut buf = Vec::new();
+        WebPEncoder::new(&mut buf).encode(data, width, height, color)?;
+        Ok(buf)
+    }
+}
+
+/// Convert supported pixel data to packed 8-bit RGB or RGBA, expanding grayscale into RGB(A) and
+/// downconverting 16-bit samples, so that libwebp (which only understands packed RGB8/RGBA8) can
+/// encode it.
+///
+/// Returns the converted buffer along with the resulting pixel layout.
+fn convert_color(data: &[u8], color: ColorType) -> ImageResult<(Vec<u8>, PixelLayout)> {
+    match color {
+        ColorType::Rgb8 => Ok((data.to_vec(), PixelLayout::Rgb)),
+        ColorType::Rgba8 => Ok((data.to_vec(), PixelLayout::Rgba)),
+        ColorType::L8 => Ok((
+            data.iter().flat_map(|&l| [l, l, l]).collect(),
+            PixelLayout::Rgb,
+        )),
+        ColorType::La8 => Ok((
+            data.chunks_exact(2)
+                .flat_map(|la| [la[0], la[0], la[0], la[1]])
+                .collect(),
+            PixelLayout::Rgba,
+        )),
+        ColorType::Rgb16 => Ok((downsample_16(data), PixelLayout::Rgb)),
+        ColorType::Rgba16 => Ok((downsample_16(data), PixelLayout::Rgba)),
+        ColorType::L16 => Ok((
+            downsample_16(data)
+                .into_iter()
+                .flat_map(|l| [l, l, l])
+                .collect(),
+            PixelLayout::Rgb,
+        )),
+        ColorType::La16 => Ok((
+            downsample_16(data)
+                .chunks_exact(2)
+                .flat_map(|la| [la[0], la[0], la[0], la[1]])
+                .collect(),
+            PixelLayout::Rgba,
+        )),
+        _ => Err(encoding_error(&format!(
+            "unsupported color type for WebP encoding: {color:?}"
+        ))),
+    }
+}
+
+/// Downconvert packed native-endian 16-bit samples to 8-bit by taking the high byte of each.
+fn downsample_16(data: &[u8]) -> Vec<u8> {
+    data.chunks_exact(2)
+        .map(|sample| (u16::from_ne_bytes([sample[0], sample[1]]) >> 8) as u8)
+        .collect()
+}
+
+/// Full libwebp encoder configuration, exposing tuning beyond what [`WebPQuality`] offers.
+///
+/// Built with a fluent, builder-style API and driven through [`WebPEncoder::new_with_config`].
+/// The config is validated (via libwebp's config-validate) when [`WebPEncoder::encode`] runs,
+/// and an invalid config is reported as an encoding error.
+#[derive(Debug, Copy, Clone)]
+pub struct WebPEncoderConfig {
+    quality: WebPQuality,
+    method: u8,
+    thread_level: bool,
+    near_lossless: u8,
+    use_sharp_yuv: bool,
+    target_size: i32,
+    target_psnr: f32,
+    filter_strength: u8,
+    filter_sharpness: u8,
+    segments: u8,
+    sns_strength: u8,
+}
+
+impl WebPEncoderConfig {
+    /// Create a new config at the given quality, with libwebp's defaults for everything else.
+    pub fn new(quality: WebPQuality) -> Self {
+        Self {
+            quality,
+            method: 4,
+            thread_level: false,
+            near_lossless: 100,
+            use_sharp_yuv: false,
+            target_size: 0,
+            target_psnr: 0.0,
+            filter_strength: 60,
+            filter_sharpness: 0,
+            segments: 4,
+            sns_strength: 50,
+        }
+    }
+
+    /// Set the quality/speed trade-off: 0 = fastest, 6 = slowest and best compression.
+    pub fn method(mut self, method: u8) -> Self {
+        self.method = method.min(6);
+        self
+    }
+
+    /// Enable multithreaded encoding where libwebp supports it.
+    pub fn thread_level(mut self, enabled: bool) -> Self {
+        self.thread_level = enabled;
+        self
+    }
+
+    /// Enable near-lossless preprocessing. `0` is maximum preprocessing, `100` disables it
+    /// (the default).
+    pub fn near_lossless(mut self, level: u8) -> Self {
+        self.near_lossless = level;
+        self
+    }
+
+    /// Use a sharper (and slower) RGB-to-YUV conversion.
+    pub fn use_sharp_yuv(mut self, enabled: bool) -> Self {
+        self.use_sharp_yuv = enabled;
+        self
+    }
+
+    /// Target an encoded size in bytes. `0` disables size targeting (the default).
+    pub fn target_size(mut self, bytes: i32) -> Self {
+        self.target_size = bytes;
+        self
+    }
+
+    /// Target a PSNR, in dB. `0.0` disables PSNR targeting (the default).
+    pub fn target_psnr(mut self, psnr: f32) -> Self {
+        self.target_psnr = psnr;
+        self
+    }
+
+    /// Set the deblocking filter strength, from 0 (off) to 100.
+    pub fn filter_strength(mut self, strength: u8) -> Self {
+        self.filter_strength = strength.min(100);
+        self
+    }
+
+    /// Set the deblocking filter sharpness, from 0 (sharpest) to 7.
+    pub fn filter_sharpness(mut self, sharpness: u8) -> Self {
+        self.filter_sharpness = sharpness.min(7);
+        self
+    }
+
+    /// Set the number of segments to partition the image into, from 1 to 4.
+    pub fn segments(mut self, segments: u8) -> Self {
+        self.segments = segments.clamp(1, 4);
+        self
+    }
+
+    /// Set the spatial noise shaping strength, from 0 (off) to 100 (maximum).
+    pub fn sns_strength(mut self, strength: u8) -> Self {
+        self.sns_strength = strength.min(100);
+        self
+    }
+
+    fn to_webp_config(self) -> ImageResult<WebPConfig> {
+        let mut config: WebPConfig = unsafe { std::mem::zeroed() };
+        if unsafe { WebPConfigInit(&mut config) } == 0 {
+            return Err(encoding_error("failed to initialize encoder config"));
+        }
+
+        let (quality, lossless) = match self.quality.0 {
+            Quality::Lossless => (100.0, 1),
+            Quality::Lossy(q) => (q as f32, 0),
+        };
+        config.quality = quality;
+        config.lossless = lossless;
+        config.method = self.method as i32;
+        config.thread_level = self.thread_level as i32;
+        config.near_lossless = self.near_lossless as i32;
+        config.use_sharp_yuv = self.use_sharp_yuv as i32;
+        config.target_size = self.target_size;
+        config.target_PSNR = self.target_psnr;
+        config.filter_strength = self.filter_strength as i32;
+        config.filter_sharpness = self.filter_sharpness as i32;
+        config.segments = self.segments as i32;
+        config.sns_strength = self.sns_strength as i32;
+
+        if unsafe { WebPValidateConfig(&config) } == 0 {
+            return Err(encoding_error("invalid encoder config"));
+        }
+
+        Ok(config)
+    }
+}
+
+/// Encode `data` through the `WebPConfig` + `WebPPicture` path, returning the writer libwebp
+/// filled with the encoded bytes.
+fn encode_with_config(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    layout: PixelLayout,
+    config: WebPEncoderConfig,
+) -> ImageResult<WebPMemoryWriter> {
+    let webp_config = config.to_webp_config()?;
+
+    let bytes_per_pixel = match layout {
+        PixelLayout::Rgb => 3,
+        PixelLayout::Rgba => 4,
+    };
+    check_buffer_len(data, width, height, bytes_per_pixel)?;
+
+    let mut picture: WebPPicture = unsafe { std::mem::zeroed() };
+    if unsafe { WebPPictureInit(&mut picture) } == 0 {
+        return Err(encoding_error("failed to initialize picture"));
+    }
+    picture.width = width as i32;
+    picture.height = height as i32;
+    picture.use_argb = 1;
+
+    let import_result = match layout {
+        PixelLayout::Rgb => unsafe {
+            WebPPictureImportRGB(&mut picture, data.as_ptr(), width as i32 * 3)
+        },
+        PixelLayout::Rgba => unsafe {
+            WebPPictureImportRGBA(&mut picture, data.as_ptr(), width as i32 * 4)
+        },
+    };
+    if import_result == 0 {
+        unsafe { WebPPictureFree(&mut picture) };
+        return Err(encoding_error("failed to import image pixels"));
+    }
+
+    let mut writer: WebPMemoryWriter = unsafe { std::mem::zeroed() };
+    unsafe { WebPMemoryWriterInit(&mut writer) };
+    picture.writer = Some(WebPMemoryWrite);
+    picture.custom_ptr = &mut writer as *mut WebPMemoryWriter as *mut std::ffi::c_void;
+
+    let encode_result = unsafe { WebPEncode(&webp_config, &mut picture) };
+    unsafe { WebPPictureFree(&mut picture) };
+
+    if encode_result == 0 {
+        unsafe { WebPMemoryWriterClear(&mut writer) };
+        return Err(encoding_error("encoding failed"));
+    }
+
+    Ok(writer)
+}
+
 impl<W: Write> ImageEncoder for WebPEncoder<W> {
     fn write_image(
         self,
@@ -117,3 +400,368 @@ impl<W: Write> ImageEncoder for WebPEncoder<W> {
         self.encode(buf, width, height, color_type)
     }
 }
+
+/// Encoder for animated WebP images.
+///
+/// Unlike [`WebPEncoder`], which emits a single still image, this assembles multiple RGBA frames
+/// into an animated WebP using libwebp's mux/animation API.
+pub struct WebPAnimationEncoder<W> {
+    inner: W,
+    raw_encoder: *mut WebPAnimEncoder,
+    width: u32,
+    height: u32,
+    last_timestamp_ms: i32,
+}
+
+/// Canvas-level options for a [`WebPAnimationEncoder`].
+///
+/// These map onto libwebp's `WebPMuxAnimParams` and must be set before the encoder is created
+/// (`WebPAnimEncoder` is opaque and cannot be reconfigured once `WebPAnimEncoderNew` has run).
+#[derive(Debug, Copy, Clone)]
+pub struct WebPAnimationOptions {
+    loop_count: u32,
+    background_color: [u8; 4],
+}
+
+impl WebPAnimationOptions {
+    /// Create new animation options: loop forever, opaque black background.
+    pub fn new() -> Self {
+        Self {
+            loop_count: 0,
+            background_color: [0, 0, 0, 255],
+        }
+    }
+
+    /// Set the number of times the animation should loop. `0` means loop forever.
+    pub fn loop_count(mut self, loop_count: u32) -> Self {
+        self.loop_count = loop_count;
+        self
+    }
+
+    /// Set the canvas background color, shown through transparent pixels between frames, as
+    /// `[r, g, b, a]`.
+    pub fn background_color(mut self, color: [u8; 4]) -> Self {
+        self.background_color = color;
+        self
+    }
+}
+
+impl Default for WebPAnimationOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pack an `[r, g, b, a]` color into libwebp's `WebPMuxAnimParams.bgcolor` representation:
+/// `(a << 24) | (r << 16) | (g << 8) | b`.
+fn pack_bgcolor(color: [u8; 4]) -> u32 {
+    let [r, g, b, a] = color;
+    u32::from_le_bytes([b, g, r, a])
+}
+
+impl<W: Write> WebPAnimationEncoder<W> {
+    /// Create a new animation encoder that writes its output to `w` once [`finish`] is called.
+    ///
+    /// `width` and `height` set the animation's canvas size; every frame pushed via
+    /// [`encode_frame`] must match these dimensions. Defaults to [`WebPAnimationOptions::default`];
+    /// use [`new_with_options`] to set a loop count or background color.
+    ///
+    /// [`finish`]: Self::finish
+    /// [`encode_frame`]: Self::encode_frame
+    /// [`new_with_options`]: Self::new_with_options
+    pub fn new(w: W, width: u32, height: u32) -> ImageResult<Self> {
+        Self::new_with_options(w, width, height, WebPAnimationOptions::default())
+    }
+
+    /// Create a new animation encoder with the given canvas-level `options`, that writes its
+    /// output to `w` once [`finish`] is called.
+    ///
+    /// [`finish`]: Self::finish
+    pub fn new_with_options(
+        w: W,
+        width: u32,
+        height: u32,
+        options: WebPAnimationOptions,
+    ) -> ImageResult<Self> {
+        let mut enc_options: WebPAnimEncoderOptions = unsafe { std::mem::zeroed() };
+        if unsafe { WebPAnimEncoderOptionsInit(&mut enc_options) } == 0 {
+            return Err(encoding_error("failed to initialize animation options"));
+        }
+
+        enc_options.anim_params = WebPMuxAnimParams {
+            bgcolor: pack_bgcolor(options.background_color),
+            loop_count: options.loop_count as i32,
+        };
+
+        let raw_encoder =
+            unsafe { WebPAnimEncoderNew(width as i32, height as i32, &enc_options) };
+        if raw_encoder.is_null() {
+            return Err(encoding_error("failed to create animation encoder"));
+        }
+
+        Ok(Self {
+            inner: w,
+            raw_encoder,
+            width,
+            height,
+            last_timestamp_ms: -1,
+        })
+    }
+
+    /// Add a frame to the animation.
+    ///
+    /// `data` holds a full canvas of pixels in `color`; any color type [`WebPEncoder::encode`]
+    /// accepts is converted to RGBA8 first.
+    ///
+    /// `timestamp_ms` is the cumulative presentation time of this frame, in milliseconds from
+    /// the start of the animation; it must strictly increase between successive calls.
+    ///
+    /// Per-frame disposal/blending is not configurable: `WebPAnimEncoderAdd` has no such
+    /// parameters, and setting them would require driving the lower-level `WebPMux` API instead
+    /// of `WebPAnimEncoder`.
+    pub fn encode_frame(
+        &mut self,
+        data: &[u8],
+        color: ColorType,
+        timestamp_ms: u32,
+    ) -> ImageResult<()> {
+        if (timestamp_ms as i32) <= self.last_timestamp_ms {
+            return Err(encoding_error("frame timestamps must strictly increase"));
+        }
+
+        let rgba = to_rgba8(data, color)?;
+        check_buffer_len(&rgba, self.width, self.height, 4)?;
+
+        let mut picture: WebPPicture = unsafe { std::mem::zeroed() };
+        if unsafe { WebPPictureInit(&mut picture) } == 0 {
+            return Err(encoding_error("failed to initialize picture"));
+        }
+        picture.use_argb = 1;
+        picture.width = self.width as i32;
+        picture.height = self.height as i32;
+
+        let import_result = unsafe {
+            WebPPictureImportRGBA(&mut picture, rgba.as_ptr(), self.width as i32 * 4)
+        };
+        if import_result == 0 {
+            unsafe { WebPPictureFree(&mut picture) };
+            return Err(encoding_error("failed to import frame pixels"));
+        }
+
+        let added = unsafe {
+            WebPAnimEncoderAdd(self.raw_encoder, &mut picture, timestamp_ms as i32, std::ptr::null())
+        };
+        unsafe { WebPPictureFree(&mut picture) };
+
+        if added == 0 {
+            return Err(encoding_error("failed to add frame to animation"));
+        }
+
+        self.last_timestamp_ms = timestamp_ms as i32;
+        Ok(())
+    }
+
+    /// Finalize the animation and write the muxed WebP bytes to the underlying writer.
+    ///
+    /// `final_timestamp_ms` is the overall duration of the animation, in milliseconds; it sets
+    /// how long the last frame added via [`encode_frame`] is displayed for, and must be greater
+    /// than that frame's own timestamp.
+    ///
+    /// This must be called after all frames have been added via [`encode_frame`].
+    ///
+    /// [`encode_frame`]: Self::encode_frame
+    pub fn finish(mut self, final_timestamp_ms: u32) -> ImageResult<()> {
+        if (final_timestamp_ms as i32) <= self.last_timestamp_ms {
+            return Err(encoding_error(
+                "final timestamp must be greater than the last frame's timestamp",
+            ));
+        }
+
+        // A final `Add(NULL, ...)` call at the animation's overall duration tells libwebp how
+        // long the last frame should be displayed for.
+        unsafe {
+            WebPAnimEncoderAdd(
+                self.raw_encoder,
+                std::ptr::null_mut(),
+                final_timestamp_ms as i32,
+                std::ptr::null(),
+            );
+        }
+
+        let mut webp_data: WebPData = unsafe { std::mem::zeroed() };
+        let assembled = unsafe { WebPAnimEncoderAssemble(self.raw_encoder, &mut webp_data) };
+        if assembled == 0 {
+            unsafe { WebPDataClear(&mut webp_data) };
+            return Err(encoding_error("failed to assemble animation"));
+        }
+
+        let bytes =
+            unsafe { std::slice::from_raw_parts(webp_data.bytes, webp_data.size) };
+        let write_result = self.inner.write_all(bytes);
+        unsafe { WebPDataClear(&mut webp_data) };
+        write_result?;
+
+        Ok(())
+    }
+}
+
+impl<W> Drop for WebPAnimationEncoder<W> {
+    fn drop(&mut self) {
+        unsafe { WebPAnimEncoderDelete(self.raw_encoder) };
+    }
+}
+
+/// Convert frame pixel data of the given color type to a tightly packed RGBA8 buffer.
+fn to_rgba8(data: &[u8], color: ColorType) -> ImageResult<Vec<u8>> {
+    let (converted, layout) = convert_color(data, color)?;
+    Ok(match layout {
+        PixelLayout::Rgba => converted,
+        PixelLayout::Rgb => converted
+            .chunks_exact(3)
+            .flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+            .collect(),
+    })
+}
+
+fn encoding_error(message: &str) -> ImageError {
+    ImageError::Encoding(EncodingError::new(ImageFormat::WebP.into(), message))
+}
+
+/// Check that `data` holds exactly `width * height * bytes_per_pixel` bytes before it is handed
+/// to a libwebp import function, which trusts `width`/`height` and reads that many bytes
+/// regardless of the buffer's actual length.
+fn check_buffer_len(data: &[u8], width: u32, height: u32, bytes_per_pixel: usize) -> ImageResult<()> {
+    let expected = width as usize * height as usize * bytes_per_pixel;
+    if data.len() != expected {
+        return Err(encoding_error(&format!(
+            "invalid buffer length {}, expected {expected} for {width}x{height} at {bytes_per_pixel} bytes/pixel",
+            data.len()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn convert_color_rgb8_passes_through() {
+        let data = [1, 2, 3, 4, 5, 6];
+        let (converted, layout) = convert_color(&data, ColorType::Rgb8).unwrap();
+        assert_eq!(converted, data);
+        assert!(matches!(layout, PixelLayout::Rgb));
+    }
+
+    #[test]
+    fn convert_color_rgba8_passes_through() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+        let (converted, layout) = convert_color(&data, ColorType::Rgba8).unwrap();
+        assert_eq!(converted, data);
+        assert!(matches!(layout, PixelLayout::Rgba));
+    }
+
+    #[test]
+    fn convert_color_l8_replicates_gray_channel() {
+        let data = [10, 20];
+        let (converted, layout) = convert_color(&data, ColorType::L8).unwrap();
+        assert_eq!(converted, vec![10, 10, 10, 20, 20, 20]);
+        assert!(matches!(layout, PixelLayout::Rgb));
+    }
+
+    #[test]
+    fn convert_color_la8_replicates_gray_and_keeps_alpha() {
+        let data = [10, 128, 20, 255];
+        let (converted, layout) = convert_color(&data, ColorType::La8).unwrap();
+        assert_eq!(converted, vec![10, 10, 10, 128, 20, 20, 20, 255]);
+        assert!(matches!(layout, PixelLayout::Rgba));
+    }
+
+    #[test]
+    fn downsample_16_takes_high_byte() {
+        let data = 0x1234u16.to_ne_bytes();
+        assert_eq!(downsample_16(&data), vec![0x12]);
+    }
+
+    #[test]
+    fn convert_color_rgb16_downconverts_to_rgb8() {
+        let data: Vec<u8> = [0x1234u16, 0x5678, 0x9abc]
+            .iter()
+            .flat_map(|s| s.to_ne_bytes())
+            .collect();
+        let (converted, layout) = convert_color(&data, ColorType::Rgb16).unwrap();
+        assert_eq!(converted, vec![0x12, 0x56, 0x9a]);
+        assert!(matches!(layout, PixelLayout::Rgb));
+    }
+
+    #[test]
+    fn convert_color_l16_downconverts_and_replicates() {
+        let data = 0xabcdu16.to_ne_bytes();
+        let (converted, layout) = convert_color(&data, ColorType::L16).unwrap();
+        assert_eq!(converted, vec![0xab, 0xab, 0xab]);
+        assert!(matches!(layout, PixelLayout::Rgb));
+    }
+
+    #[test]
+    fn convert_color_rejects_unsupported_type() {
+        assert!(convert_color(&[0; 4], ColorType::Rgb32F).is_err());
+    }
+
+    #[test]
+    fn encode_to_vec_round_trips_through_write_all() {
+        let width = 2;
+        let height = 2;
+        let data = [255u8; 2 * 2 * 4];
+
+        let via_vec =
+            WebPEncoder::encode_to_vec(&data, width, height, ColorType::Rgba8).unwrap();
+
+        let mut via_writer = Vec::new();
+        WebPEncoder::new(&mut via_writer)
+            .encode(&data, width, height, ColorType::Rgba8)
+            .unwrap();
+
+        assert!(!via_vec.is_empty());
+        assert_eq!(via_vec, via_writer);
+    }
+
+    #[test]
+    fn pack_bgcolor_matches_a_r_g_b_order() {
+        assert_eq!(pack_bgcolor([0x11, 0x22, 0x33, 0x44]), 0x4411_2233);
+        assert_eq!(pack_bgcolor([0, 0, 0, 0]), 0);
+        assert_eq!(pack_bgcolor([0xff, 0xff, 0xff, 0xff]), 0xffff_ffff);
+    }
+
+    #[test]
+    fn animation_encoder_round_trips_through_encode_frame_and_finish() {
+        let width = 2;
+        let height = 2;
+        let frame = [255u8; 2 * 2 * 4];
+
+        let mut out = Vec::new();
+        let mut encoder = WebPAnimationEncoder::new_with_options(
+            &mut out,
+            width,
+            height,
+            WebPAnimationOptions::new()
+                .loop_count(1)
+                .background_color([0, 0, 0, 255]),
+        )
+        .unwrap();
+        encoder.encode_frame(&frame, ColorType::Rgba8, 0).unwrap();
+        encoder.encode_frame(&frame, ColorType::Rgba8, 100).unwrap();
+        encoder.finish(200).unwrap();
+
+        assert!(!out.is_empty());
+    }
+
+    #[test]
+    fn encode_frame_rejects_undersized_buffer() {
+        let mut out = Vec::new();
+        let mut encoder = WebPAnimationEncoder::new(&mut out, 10, 10).unwrap();
+        let undersized = [0u8; 4];
+        assert!(encoder
+            .encode_frame(&undersized, ColorType::Rgba8, 0)
+            .is_err());
+    }
+}